@@ -1,7 +1,12 @@
 //! These macros are used cede generation in Solana smart contracts
 //!
 //! Currently, the implemented macros can generate static program IDs
-//! and deterministic program derived addresses (the bump seed is autogenerated).
+//! and deterministic program derived addresses (the bump seed is autogenerated),
+//! including signer-seed helpers for `invoke_signed`. `declare_id`/`declare_pda` target
+//! `solana_program::pubkey::Pubkey` by default; the `_with_type` variants let downstream
+//! crates and Solana forks point the generated code at their own pubkey type instead.
+//! `pubkey!` decodes a base58 literal to a `Pubkey` in const contexts, and
+//! `declare_deprecated_id` keeps a rotated-out program ID referenceable as `#[deprecated]`.
 //!
 //! The code is forked from the Solana SDK (https://github.com/solana-labs/solana/blob/master/sdk/macro/src/lib.rs)
 //! and modified to support new features.
@@ -18,15 +23,33 @@ use {
     solana_program::pubkey::Pubkey,
     std::convert::TryFrom,
     syn::{
+        bracketed,
         parse::{Parse, ParseStream, Result},
-        parse_macro_input, Expr, LitByte, LitStr, Token,
+        parse_macro_input,
+        punctuated::Punctuated,
+        Expr, LitByte, LitByteStr, LitInt, LitStr, Token,
     },
 };
 
-fn parse_id(input: ParseStream) -> Result<proc_macro2::TokenStream> {
+/// Maximum number of seeds a PDA derivation may use, matching `solana_program::pubkey::MAX_SEEDS`.
+const MAX_SEEDS: usize = 16;
+/// Maximum length, in bytes, of a single seed, matching `solana_program::pubkey::MAX_SEED_LEN`.
+const MAX_SEED_LEN: usize = 32;
+
+/// The pubkey type `declare_id!`/`declare_pda!` emit code against when no explicit
+/// `_with_type` override is given.
+fn default_pubkey_type() -> proc_macro2::TokenStream {
+    quote! { ::solana_program::pubkey::Pubkey }
+}
+
+fn parse_id_with_type(
+    input: ParseStream,
+    pubkey_type: proc_macro2::TokenStream,
+) -> Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
     let id = if input.peek(syn::LitStr) {
         let id_literal: LitStr = input.parse()?;
-        parse_pubkey(&id_literal)?
+        let array = parse_pubkey_array(&id_literal)?;
+        quote! { #pubkey_type::new_from_array(#array) }
     } else {
         let expr: Expr = input.parse()?;
         quote! { #expr }
@@ -36,10 +59,12 @@ fn parse_id(input: ParseStream) -> Result<proc_macro2::TokenStream> {
         let stream: proc_macro2::TokenStream = input.parse()?;
         return Err(syn::Error::new_spanned(stream, "unexpected token"));
     }
-    Ok(id)
+    Ok((pubkey_type, id))
 }
 
-fn parse_pubkey(id_literal: &LitStr) -> Result<proc_macro2::TokenStream> {
+/// Decodes a base58 pubkey literal into a `[u8; 32]` array expression, with no pubkey
+/// type wrapped around it.
+fn parse_pubkey_array(id_literal: &LitStr) -> Result<proc_macro2::TokenStream> {
     let id_vec = bs58::decode(id_literal.value())
         .into_vec()
         .map_err(|_| syn::Error::new_spanned(id_literal, "failed to decode base58 string"))?;
@@ -51,24 +76,168 @@ fn parse_pubkey(id_literal: &LitStr) -> Result<proc_macro2::TokenStream> {
     })?;
     let bytes = id_array.iter().map(|b| LitByte::new(*b, Span::call_site()));
     Ok(quote! {
-        ::solana_program::pubkey::Pubkey::new_from_array(
-            [#(#bytes,)*]
-        )
+        [#(#bytes,)*]
     })
 }
 
+/// Decodes a base58 pubkey literal at macro-expansion time, wrapped in the default
+/// `::solana_program::pubkey::Pubkey` type.
+fn parse_pubkey(id_literal: &LitStr) -> Result<proc_macro2::TokenStream> {
+    let pubkey_type = default_pubkey_type();
+    let array = parse_pubkey_array(id_literal)?;
+    Ok(quote! { #pubkey_type::new_from_array(#array) })
+}
+
+/// A single PDA seed, parsed from a UTF-8 string literal, a byte-string literal, a
+/// `pubkey!(...)`-wrapped base58 pubkey literal, or a suffixed integer literal.
+struct SeedLit {
+    bytes: Vec<u8>,
+    span: Span,
+}
+
+impl Parse for SeedLit {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitByteStr) {
+            let lit: LitByteStr = input.parse()?;
+            return Ok(Self {
+                bytes: lit.value(),
+                span: lit.span(),
+            });
+        }
+
+        if input.peek(LitInt) {
+            let lit: LitInt = input.parse()?;
+            let bytes = int_seed_bytes(&lit)?;
+            return Ok(Self {
+                bytes,
+                span: lit.span(),
+            });
+        }
+
+        // `pubkey!("Base58...")` is the explicit opt-in for a pubkey seed, decoded to
+        // its raw 32 bytes; a bare string literal always means its UTF-8 bytes, with
+        // no auto-detection, so a plain seed's meaning never changes out from under it.
+        if input.peek(syn::Ident) && input.peek2(Token![!]) {
+            let ident: syn::Ident = input.fork().parse()?;
+            if ident == "pubkey" {
+                input.parse::<syn::Ident>()?;
+                input.parse::<Token![!]>()?;
+                let content;
+                syn::parenthesized!(content in input);
+                let lit: LitStr = content.parse()?;
+                if !content.is_empty() {
+                    return Err(content.error("unexpected token"));
+                }
+                let decoded = bs58::decode(lit.value()).into_vec().map_err(|_| {
+                    syn::Error::new_spanned(&lit, "failed to decode base58 string")
+                })?;
+                if decoded.len() != 32 {
+                    return Err(syn::Error::new_spanned(
+                        &lit,
+                        format!("pubkey seed is not 32 bytes long: len={}", decoded.len()),
+                    ));
+                }
+                return Ok(Self {
+                    bytes: decoded,
+                    span: lit.span(),
+                });
+            }
+        }
+
+        let lit: LitStr = input.parse()?;
+        Ok(Self {
+            bytes: lit.value().into_bytes(),
+            span: lit.span(),
+        })
+    }
+}
+
+/// Encodes an integer seed literal as its fixed-width little-endian byte representation.
+/// The literal must carry a `u8`/`u16`/`u32`/`u64` suffix so the seed width is explicit.
+fn int_seed_bytes(lit: &LitInt) -> Result<Vec<u8>> {
+    match lit.suffix() {
+        "u8" => Ok(vec![lit.base10_parse::<u8>()?]),
+        "u16" => Ok(lit.base10_parse::<u16>()?.to_le_bytes().to_vec()),
+        "u32" => Ok(lit.base10_parse::<u32>()?.to_le_bytes().to_vec()),
+        "u64" => Ok(lit.base10_parse::<u64>()?.to_le_bytes().to_vec()),
+        "" => Err(syn::Error::new_spanned(
+            lit,
+            "integer seed requires a width suffix (u8, u16, u32, or u64)",
+        )),
+        other => Err(syn::Error::new_spanned(
+            lit,
+            format!(
+                "unsupported integer seed suffix `{}`; expected u8, u16, u32, or u64",
+                other
+            ),
+        )),
+    }
+}
+
+/// Validates a parsed seed list against the same `MAX_SEEDS` / `MAX_SEED_LEN` limits as
+/// `find_program_address` and unwraps each seed to its raw bytes.
+fn validate_seeds(seed_lits: Vec<SeedLit>, list_span: Span) -> Result<Vec<Vec<u8>>> {
+    if seed_lits.len() > MAX_SEEDS {
+        return Err(syn::Error::new(
+            list_span,
+            format!(
+                "too many seeds: max {} allowed, got {}",
+                MAX_SEEDS,
+                seed_lits.len()
+            ),
+        ));
+    }
+
+    let mut seeds = Vec::with_capacity(seed_lits.len());
+    for seed in seed_lits {
+        if seed.bytes.len() > MAX_SEED_LEN {
+            return Err(syn::Error::new(
+                seed.span,
+                format!(
+                    "seed is too long: max {} bytes allowed, got {}",
+                    MAX_SEED_LEN,
+                    seed.bytes.len()
+                ),
+            ));
+        }
+        seeds.push(seed.bytes);
+    }
+
+    Ok(seeds)
+}
+
+/// Parses either a bracketed, comma-separated list of seeds, e.g.
+/// `["prefix", "vault", b"\x01\x02", pubkey!("SomeBase58..."), 0u64]`, or a single bare
+/// seed (the pre-existing `declare_pda!("Pda", "Program", "seed")` syntax, kept working
+/// as a one-element list). Each element is a UTF-8 string, a byte string, a
+/// `pubkey!(...)`-wrapped base58 pubkey (decoded to its raw 32 bytes), or a suffixed
+/// integer literal (encoded little-endian).
+fn parse_seeds(input: ParseStream) -> Result<Vec<Vec<u8>>> {
+    if input.peek(syn::token::Bracket) {
+        let content;
+        bracketed!(content in input);
+        let list_span = content.span();
+        let seed_lits: Punctuated<SeedLit, Token![,]> = Punctuated::parse_terminated(&content)?;
+        return validate_seeds(seed_lits.into_iter().collect(), list_span);
+    }
+
+    let seed: SeedLit = input.parse()?;
+    let span = seed.span;
+    validate_seeds(vec![seed], span)
+}
+
 fn parse_pda(
     id_literal: &LitStr,
     program_id: &LitStr,
-    seed: &LitStr,
+    seeds: &[Vec<u8>],
 ) -> Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
     let pda_key = Pubkey::from_str(&id_literal.value())
         .map_err(|_| syn::Error::new_spanned(id_literal, "failed to decode base58 string"))?;
     let program_id = Pubkey::from_str(&program_id.value())
         .map_err(|_| syn::Error::new_spanned(id_literal, "failed to decode base58 string"))?;
 
-    let (computed_key, bump_seed) =
-        Pubkey::find_program_address(&[&seed.value().as_ref()], &program_id);
+    let seed_refs: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    let (computed_key, bump_seed) = Pubkey::find_program_address(&seed_refs, &program_id);
 
     if pda_key != computed_key {
         return Err(syn::Error::new_spanned(
@@ -77,35 +246,49 @@ fn parse_pda(
         ));
     }
 
-    let pda_token_stream = parse_pubkey(id_literal)?;
+    let pda_array = parse_pubkey_array(id_literal)?;
 
     let bump = LitByte::new(bump_seed, Span::call_site());
     let bump_token_stream = quote! {
         #bump
     };
-    Ok((pda_token_stream, bump_token_stream))
+    Ok((pda_array, bump_token_stream))
 }
 
 fn generate_static_pubkey_code(
+    pubkey_type: &proc_macro2::TokenStream,
     id: &proc_macro2::TokenStream,
+    deprecated: Option<&str>,
     tokens: &mut proc_macro2::TokenStream,
 ) {
+    let deprecated_attr = deprecated.map(|note| quote! { #[deprecated(note = #note)] });
+    // check_id/id() reference ID themselves; without this they'd trip the very
+    // deprecation warning #deprecated_attr puts on ID, even for callers who never
+    // touch either function.
+    let allow_self_reference = deprecated.map(|_| quote! { #[allow(deprecated)] });
+
     tokens.extend(quote! {
         /// The static program ID
-        pub static ID: ::solana_program::pubkey::Pubkey = #id;
+        #deprecated_attr
+        pub const ID: #pubkey_type = #id;
 
         /// Confirms that a given pubkey is equivalent to the program ID
-        pub fn check_id(id: &::solana_program::pubkey::Pubkey) -> bool {
+        #deprecated_attr
+        #allow_self_reference
+        pub fn check_id(id: &#pubkey_type) -> bool {
             id == &ID
         }
 
         /// Returns the program ID
-        pub fn id() -> ::solana_program::pubkey::Pubkey {
+        #deprecated_attr
+        #allow_self_reference
+        pub fn id() -> #pubkey_type {
             ID
         }
 
         #[cfg(test)]
         #[test]
+        #[allow(deprecated)]
         fn test_id() {
             assert!(check_id(&id()));
         }
@@ -122,44 +305,180 @@ fn generate_static_bump_code(
     });
 }
 
-struct Id(proc_macro2::TokenStream);
+/// Generates `SEED_n` byte-string constants for each seed plus `signer_seeds()` /
+/// `signer_seeds_with_bump()` helpers, so callers can build the `&[&[&[u8]]]` that
+/// `invoke_signed` expects without re-deriving the seeds by hand.
+fn generate_static_signer_seeds_code(
+    seeds: &[Vec<u8>],
+    tokens: &mut proc_macro2::TokenStream,
+) {
+    let seed_names: Vec<syn::Ident> = (0..seeds.len())
+        .map(|i| syn::Ident::new(&format!("SEED_{}", i), Span::call_site()))
+        .collect();
+    let seed_consts = seeds
+        .iter()
+        .map(|seed| LitByteStr::new(seed, Span::call_site()));
+    let len = seeds.len() + 1;
+
+    tokens.extend(quote! {
+        #(
+            /// One of the literal seed byte strings used to derive the static PDA.
+            pub const #seed_names: &[u8] = #seed_consts;
+        )*
+
+        /// Returns the seeds (including the bump) used to sign CPIs for the static PDA,
+        /// e.g. `invoke_signed(&ix, &accounts, &[&MY_PDA::signer_seeds()])`.
+        pub fn signer_seeds() -> [&'static [u8]; #len] {
+            [#(#seed_names,)* &[BUMP]]
+        }
+
+        /// Returns `(signer_seeds(), BUMP)` for callers that also need the bump on its own.
+        pub fn signer_seeds_with_bump() -> ([&'static [u8]; #len], u8) {
+            (signer_seeds(), BUMP)
+        }
+    });
+}
+
+struct Id {
+    pubkey_type: proc_macro2::TokenStream,
+    id: proc_macro2::TokenStream,
+}
 
 impl Parse for Id {
     fn parse(input: ParseStream) -> Result<Self> {
-        parse_id(input).map(Self)
+        let (pubkey_type, id) = parse_id_with_type(input, default_pubkey_type())?;
+        Ok(Self { pubkey_type, id })
     }
 }
 
 impl ToTokens for Id {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        generate_static_pubkey_code(&self.0, tokens)
+        generate_static_pubkey_code(&self.pubkey_type, &self.id, None, tokens)
+    }
+}
+
+/// Parses `declare_id_with_type!(SomeCrate::Pubkey, "Base58...")`, letting downstream
+/// crates (Anchor-style re-exports, Solana forks) generate code against their own
+/// pubkey type instead of `::solana_program::pubkey::Pubkey`.
+struct IdWithType(Id);
+
+impl Parse for IdWithType {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let pubkey_type: syn::Path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let (pubkey_type, id) = parse_id_with_type(input, quote! { #pubkey_type })?;
+        Ok(Self(Id { pubkey_type, id }))
+    }
+}
+
+impl ToTokens for IdWithType {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.0.to_tokens(tokens)
+    }
+}
+
+/// Parses `declare_deprecated_id!("OldBase58...")`: like `declare_id!`, but marks the
+/// generated `ID`, `id()` and `check_id` as `#[deprecated]`, for program IDs that have
+/// been rotated but must remain referenceable during migration.
+struct DeprecatedId(Id);
+
+impl Parse for DeprecatedId {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let (pubkey_type, id) = parse_id_with_type(input, default_pubkey_type())?;
+        Ok(Self(Id { pubkey_type, id }))
+    }
+}
+
+impl ToTokens for DeprecatedId {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        generate_static_pubkey_code(
+            &self.0.pubkey_type,
+            &self.0.id,
+            Some("this program ID has been rotated; use the current program ID instead"),
+            tokens,
+        );
+    }
+}
+
+struct PubkeyExpr(proc_macro2::TokenStream);
+
+impl Parse for PubkeyExpr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let id_literal: LitStr = input.parse()?;
+        if !input.is_empty() {
+            let stream: proc_macro2::TokenStream = input.parse()?;
+            return Err(syn::Error::new_spanned(stream, "unexpected token"));
+        }
+        parse_pubkey(&id_literal).map(Self)
+    }
+}
+
+impl ToTokens for PubkeyExpr {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.0.to_tokens(tokens)
     }
 }
 
 struct ProgramPdaArgs {
+    pubkey_type: proc_macro2::TokenStream,
     pda: proc_macro2::TokenStream,
     bump: proc_macro2::TokenStream,
+    seeds: Vec<Vec<u8>>,
+}
+
+fn parse_pda_args(
+    input: ParseStream,
+    pubkey_type: proc_macro2::TokenStream,
+) -> Result<ProgramPdaArgs> {
+    let pda_address: LitStr = input.parse()?;
+    input.parse::<Token![,]>()?;
+    let program_id: LitStr = input.parse()?;
+    input.parse::<Token![,]>()?;
+    let seeds = parse_seeds(input)?;
+    if !input.is_empty() {
+        return Err(input.error("unexpected token"));
+    }
+    let (pda, bump) = parse_pda(&pda_address, &program_id, &seeds)?;
+    Ok(ProgramPdaArgs {
+        pubkey_type,
+        pda,
+        bump,
+        seeds,
+    })
 }
 
 impl Parse for ProgramPdaArgs {
     fn parse(input: ParseStream) -> Result<Self> {
-        let pda_address: LitStr = input.parse()?;
-        input.parse::<Token![,]>()?;
-        let program_id: LitStr = input.parse()?;
-        input.parse::<Token![,]>()?;
-        let seed: LitStr = input.parse()?;
-        if !input.is_empty() {
-            return Err(input.error("unexpected token"));
-        }
-        let (pda, bump) = parse_pda(&pda_address, &program_id, &seed)?;
-        Ok(Self { pda, bump })
+        parse_pda_args(input, default_pubkey_type())
     }
 }
 
 impl ToTokens for ProgramPdaArgs {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let pubkey_type = &self.pubkey_type;
+        let pda = &self.pda;
+        let pda_expr = quote! { #pubkey_type::new_from_array(#pda) };
         generate_static_bump_code(&self.bump, tokens);
-        generate_static_pubkey_code(&self.pda, tokens)
+        generate_static_pubkey_code(pubkey_type, &pda_expr, None, tokens);
+        generate_static_signer_seeds_code(&self.seeds, tokens);
+    }
+}
+
+/// Parses `declare_pda_with_type!(SomeCrate::Pubkey, "PdaPubkey", "ProgramId", [...])`,
+/// the PDA counterpart to [`IdWithType`].
+struct ProgramPdaArgsWithType(ProgramPdaArgs);
+
+impl Parse for ProgramPdaArgsWithType {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let pubkey_type: syn::Path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        parse_pda_args(input, quote! { #pubkey_type }).map(Self)
+    }
+}
+
+impl ToTokens for ProgramPdaArgsWithType {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.0.to_tokens(tokens)
     }
 }
 
@@ -173,4 +492,39 @@ pub fn declare_id(input: TokenStream) -> TokenStream {
 pub fn declare_pda(input: TokenStream) -> TokenStream {
     let id = parse_macro_input!(input as ProgramPdaArgs);
     TokenStream::from(quote! {#id})
+}
+
+/// Like `declare_id!`, but generates code against the given pubkey type instead of
+/// `::solana_program::pubkey::Pubkey`, e.g. `declare_id_with_type!(domino_program::Pubkey, "...")`.
+#[proc_macro]
+pub fn declare_id_with_type(input: TokenStream) -> TokenStream {
+    let id = parse_macro_input!(input as IdWithType);
+    TokenStream::from(quote! {#id})
+}
+
+/// Declares a program ID that has been rotated out, keeping it referenceable as a
+/// compiler-flagged `#[deprecated]` item instead of a silent stale constant.
+#[proc_macro]
+pub fn declare_deprecated_id(input: TokenStream) -> TokenStream {
+    let id = parse_macro_input!(input as DeprecatedId);
+    TokenStream::from(quote! {#id})
+}
+
+/// Like `declare_pda!`, but generates code against the given pubkey type instead of
+/// `::solana_program::pubkey::Pubkey`.
+#[proc_macro]
+pub fn declare_pda_with_type(input: TokenStream) -> TokenStream {
+    let id = parse_macro_input!(input as ProgramPdaArgsWithType);
+    TokenStream::from(quote! {#id})
+}
+
+/// Decodes a base58 pubkey literal at macro-expansion time and expands to a
+/// `Pubkey::new_from_array([...])` expression, usable in const contexts such as `const`
+/// and `static` initializers, e.g. `const AUTHORITY: Pubkey = pubkey!("...");`. The
+/// expansion is a call expression, not a pattern, so it cannot stand directly in a
+/// match arm; match on a `const` declared with it instead.
+#[proc_macro]
+pub fn pubkey(input: TokenStream) -> TokenStream {
+    let id = parse_macro_input!(input as PubkeyExpr);
+    TokenStream::from(quote! {#id})
 }
\ No newline at end of file